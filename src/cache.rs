@@ -0,0 +1,175 @@
+//! TTL cache over master server queries, so long-running tools don't have to
+//! re-query the same master on every poll.
+
+use std::num::NonZeroUsize;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use lru::LruCache;
+
+use crate::command::server_addresses_with;
+use crate::server_address::ServerAddress;
+use crate::tinyudp::{Transport, UdpTransport};
+
+const DEFAULT_CAPACITY: usize = 64;
+const DEFAULT_TTL: Duration = Duration::from_secs(10 * 60);
+
+struct CacheEntry {
+    addresses: Vec<ServerAddress>,
+    fetched_at: Instant,
+}
+
+/// Caches [`server_addresses`](crate::server_addresses) results per master,
+/// refreshing once the configured TTL has elapsed.
+pub struct MasterCache {
+    entries: Mutex<LruCache<String, CacheEntry>>,
+    ttl: Duration,
+}
+
+impl MasterCache {
+    /// Create a cache holding up to `capacity` masters (at least one), each
+    /// fresh for `ttl`.
+    pub fn new(capacity: usize, ttl: Duration) -> Self {
+        let capacity = NonZeroUsize::new(capacity).unwrap_or(NonZeroUsize::new(1).unwrap());
+        Self {
+            entries: Mutex::new(LruCache::new(capacity)),
+            ttl,
+        }
+    }
+
+    /// Get server addresses for `master_address`, returning the cached list
+    /// if still fresh and otherwise performing a real query and storing it.
+    pub async fn cached_server_addresses(
+        &self,
+        master_address: &str,
+        timeout: Duration,
+    ) -> Result<Vec<ServerAddress>> {
+        self.cached_server_addresses_with(&UdpTransport, master_address, timeout)
+            .await
+    }
+
+    /// Same as [`Self::cached_server_addresses`], but over a caller-supplied
+    /// [`Transport`] instead of a real UDP socket — useful for testing
+    /// without network access.
+    pub async fn cached_server_addresses_with(
+        &self,
+        transport: &impl Transport,
+        master_address: &str,
+        timeout: Duration,
+    ) -> Result<Vec<ServerAddress>> {
+        if let Some(addresses) = self.fresh_entry(master_address) {
+            return Ok(addresses);
+        }
+
+        let addresses = server_addresses_with(transport, master_address, timeout).await?;
+
+        self.entries.lock().unwrap().put(
+            master_address.to_string(),
+            CacheEntry {
+                addresses: addresses.clone(),
+                fetched_at: Instant::now(),
+            },
+        );
+
+        Ok(addresses)
+    }
+
+    fn fresh_entry(&self, master_address: &str) -> Option<Vec<ServerAddress>> {
+        let mut entries = self.entries.lock().unwrap();
+        let entry = entries.get(master_address)?;
+
+        if entry.fetched_at.elapsed() < self.ttl {
+            Some(entry.addresses.clone())
+        } else {
+            None
+        }
+    }
+}
+
+impl Default for MasterCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_CAPACITY, DEFAULT_TTL)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[derive(Default)]
+    struct CountingTransport {
+        requests: AtomicUsize,
+    }
+
+    impl Transport for CountingTransport {
+        async fn request(
+            &self,
+            _target: &str,
+            _msg: &[u8],
+            _opts: crate::tinyudp::Options,
+        ) -> Result<Vec<u8>> {
+            self.requests.fetch_add(1, Ordering::SeqCst);
+            Ok(vec![
+                0xff, 0xff, 0xff, 0xff, 0x64, 0x0a, 192, 168, 1, 1, 0x75, 0x30,
+            ])
+        }
+    }
+
+    #[tokio::test]
+    async fn test_cached_server_addresses_reuses_fresh_entry() -> Result<()> {
+        let cache = MasterCache::new(4, Duration::from_secs(60));
+        let transport = CountingTransport::default();
+
+        let first = cache
+            .cached_server_addresses_with(&transport, "master:27000", Duration::from_secs(1))
+            .await?;
+        let second = cache
+            .cached_server_addresses_with(&transport, "master:27000", Duration::from_secs(1))
+            .await?;
+
+        assert_eq!(first, second);
+        assert_eq!(transport.requests.load(Ordering::SeqCst), 1);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_cached_server_addresses_requeries_after_ttl() -> Result<()> {
+        let cache = MasterCache::new(4, Duration::from_millis(10));
+        let transport = CountingTransport::default();
+
+        cache
+            .cached_server_addresses_with(&transport, "master:27000", Duration::from_secs(1))
+            .await?;
+        tokio::time::sleep(Duration::from_millis(30)).await;
+        cache
+            .cached_server_addresses_with(&transport, "master:27000", Duration::from_secs(1))
+            .await?;
+
+        assert_eq!(transport.requests.load(Ordering::SeqCst), 2);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_cached_server_addresses_evicts_least_recently_used() -> Result<()> {
+        let cache = MasterCache::new(1, Duration::from_secs(60));
+        let transport = CountingTransport::default();
+
+        cache
+            .cached_server_addresses_with(&transport, "master-a:27000", Duration::from_secs(1))
+            .await?;
+        cache
+            .cached_server_addresses_with(&transport, "master-b:27000", Duration::from_secs(1))
+            .await?;
+        cache
+            .cached_server_addresses_with(&transport, "master-a:27000", Duration::from_secs(1))
+            .await?;
+
+        // capacity 1 evicted master-a after master-b was inserted, so
+        // master-a required a third real request.
+        assert_eq!(transport.requests.load(Ordering::SeqCst), 3);
+        Ok(())
+    }
+}
@@ -2,9 +2,18 @@
 //!
 //! Get server addresses from QuakeWorld master servers.
 
+mod cache;
+mod command;
 mod query;
 mod query_multiple;
 mod server_address;
+mod serverinfo;
+mod tinyudp;
 
+pub use crate::cache::MasterCache;
+pub use crate::command::{server_addresses, server_addresses_from_many};
 pub use crate::query::query;
 pub use crate::query_multiple::{MultiQueryResult, query_multiple};
+pub use crate::server_address::ServerAddress;
+pub use crate::serverinfo::{ServerInfo, ServerResult, ServerResultKind, query_servers};
+pub use crate::tinyudp::{Transport, UdpTransport};
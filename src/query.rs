@@ -4,6 +4,7 @@ use anyhow::{Result, anyhow as e};
 use binrw::BinRead;
 
 use crate::server_address::RawServerAddress;
+use crate::tinyudp::{self, Transport, UdpTransport};
 
 /// Get server addresses from a single master server
 ///
@@ -22,16 +23,29 @@ use crate::server_address::RawServerAddress;
 /// }
 /// ```
 pub async fn query(master_address: &str, timeout: Duration) -> Result<Vec<String>> {
+    query_with(&UdpTransport, master_address, timeout).await
+}
+
+/// Same as [`query`], but over a caller-supplied [`Transport`] instead of a
+/// real UDP socket — useful for testing without network access.
+pub async fn query_with(
+    transport: &impl Transport,
+    master_address: &str,
+    timeout: Duration,
+) -> Result<Vec<String>> {
     const STATUS_MSG: [u8; 3] = [99, 10, 0];
-    let response = tinyudp::send_and_receive(
-        master_address,
-        &STATUS_MSG,
-        tinyudp::ReadOptions {
-            timeout,
-            buffer_size: 64 * 1024, // 64 kb
-        },
-    )
-    .await?;
+    let response = transport
+        .request(
+            master_address,
+            &STATUS_MSG,
+            tinyudp::Options {
+                timeout,
+                buffer_size: 64 * 1024, // 64 kb
+                retries: 0,
+                retry_delay: Duration::ZERO,
+            },
+        )
+        .await?;
     parse_response(&response)
 }
 
@@ -55,6 +69,7 @@ fn parse_response(response: &[u8]) -> Result<Vec<String>> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::tinyudp::test_support::FixedResponseTransport;
     use pretty_assertions::assert_eq;
 
     #[tokio::test]
@@ -90,4 +105,17 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_query_with_mock_transport() -> Result<()> {
+        let response = vec![
+            0xff, 0xff, 0xff, 0xff, 0x64, 0x0a, 192, 168, 1, 1, 0x75, 0x30,
+        ];
+        let transport = FixedResponseTransport(response);
+
+        let result = query_with(&transport, "unused:0", Duration::from_secs(1)).await?;
+
+        assert_eq!(result, vec!["192.168.1.1:30000".to_string()]);
+        Ok(())
+    }
 }
@@ -2,8 +2,10 @@ use futures::future;
 use std::time::Duration;
 
 use crate::query;
+use crate::tinyudp::{Transport, UdpTransport};
 
 #[derive(Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct QuerySuccess {
     master_address: String,
     server_addresses: Vec<String>,
@@ -19,8 +21,10 @@ impl QuerySuccess {
 }
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct QueryFailure {
     master_address: String,
+    #[cfg_attr(feature = "serde", serde(serialize_with = "serialize_error"))]
     error: anyhow::Error,
 }
 impl QueryFailure {
@@ -33,6 +37,15 @@ impl QueryFailure {
     }
 }
 
+/// Serialize an [`anyhow::Error`] as its display string.
+#[cfg(feature = "serde")]
+fn serialize_error<S>(error: &anyhow::Error, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    serializer.collect_str(error)
+}
+
 #[derive(Debug, Default)]
 pub struct MultiQueryResult {
     /// Collection of successful queries.
@@ -42,6 +55,31 @@ pub struct MultiQueryResult {
     failures: Vec<QueryFailure>,
 }
 
+#[cfg(feature = "serde")]
+impl serde::Serialize for MultiQueryResult {
+    /// Serializes the successes and failures alongside the deduped
+    /// `server_addresses()`, so consumers can emit the whole multi-master
+    /// result as JSON without calling any accessors themselves.
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        #[derive(serde::Serialize)]
+        struct Json<'a> {
+            successes: &'a [QuerySuccess],
+            failures: &'a [QueryFailure],
+            server_addresses: Vec<String>,
+        }
+
+        Json {
+            successes: &self.successes,
+            failures: &self.failures,
+            server_addresses: self.server_addresses(),
+        }
+        .serialize(serializer)
+    }
+}
+
 impl MultiQueryResult {
     /// Iterator over successful query results.
     pub fn successful_queries(&self) -> impl Iterator<Item = &QuerySuccess> {
@@ -89,9 +127,22 @@ impl MultiQueryResult {
 /// }
 /// ```
 pub async fn query_multiple(master_addresses: &[String], timeout: Duration) -> MultiQueryResult {
-    let tasks = master_addresses
-        .iter()
-        .map(|address| async move { (address.clone(), query(address, timeout).await) });
+    query_multiple_with(&UdpTransport, master_addresses, timeout).await
+}
+
+/// Same as [`query_multiple`], but over a caller-supplied [`Transport`]
+/// instead of real UDP sockets — useful for testing without network access.
+pub async fn query_multiple_with(
+    transport: &(impl Transport + Sync),
+    master_addresses: &[String],
+    timeout: Duration,
+) -> MultiQueryResult {
+    let tasks = master_addresses.iter().map(|address| async move {
+        (
+            address.clone(),
+            query::query_with(transport, address, timeout).await,
+        )
+    });
 
     let mut results = MultiQueryResult::default();
 
@@ -114,6 +165,7 @@ pub async fn query_multiple(master_addresses: &[String], timeout: Duration) -> M
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::tinyudp::test_support::FixedResponseTransport;
     use anyhow::Result;
     use pretty_assertions::assert_eq;
 
@@ -151,4 +203,65 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_query_multiple_with_mock_transport() {
+        let response = vec![
+            0xff, 0xff, 0xff, 0xff, 0x64, 0x0a, 192, 168, 1, 1, 0x75, 0x30,
+        ];
+        let transport = FixedResponseTransport(response);
+        let master_addresses = vec!["unused:0".to_string()];
+
+        let results =
+            query_multiple_with(&transport, &master_addresses, Duration::from_secs(1)).await;
+
+        assert_eq!(results.server_addresses(), vec!["192.168.1.1:30000"]);
+    }
+
+    #[cfg(feature = "serde")]
+    struct FlakyTransport {
+        response: Vec<u8>,
+        fail_target: &'static str,
+    }
+
+    #[cfg(feature = "serde")]
+    impl Transport for FlakyTransport {
+        async fn request(
+            &self,
+            target: &str,
+            _msg: &[u8],
+            _opts: crate::tinyudp::Options,
+        ) -> anyhow::Result<Vec<u8>> {
+            if target == self.fail_target {
+                Err(anyhow::anyhow!("mock failure for {target}"))
+            } else {
+                Ok(self.response.clone())
+            }
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[tokio::test]
+    async fn test_multi_query_result_serializes_as_json() {
+        let response = vec![
+            0xff, 0xff, 0xff, 0xff, 0x64, 0x0a, 192, 168, 1, 1, 0x75, 0x30,
+        ];
+        let transport = FlakyTransport {
+            response,
+            fail_target: "INVALID:0",
+        };
+        let master_addresses = vec!["unused:0".to_string(), "INVALID:0".to_string()];
+
+        let results =
+            query_multiple_with(&transport, &master_addresses, Duration::from_secs(1)).await;
+
+        let json = serde_json::to_value(&results).unwrap();
+        assert_eq!(
+            json["server_addresses"],
+            serde_json::json!(["192.168.1.1:30000"])
+        );
+        assert_eq!(json["successes"][0]["master_address"], "unused:0");
+        assert_eq!(json["failures"][0]["master_address"], "INVALID:0");
+        assert!(json["failures"][0]["error"].is_string());
+    }
 }
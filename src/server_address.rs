@@ -1,44 +1,176 @@
 use binrw::BinRead;
 use std::fmt::Display;
+use std::net::Ipv6Addr;
 
+/// Marker some Xash-style masters place before an IPv6 record to distinguish
+/// it from the plain 6-byte IPv4 record.
+///
+/// This has to be a value no real IPv4 record can ever produce. A single
+/// marker byte can't guarantee that — it would collide with the first octet
+/// of a legitimate V4 address (e.g. `6.1.2.3`) and silently swallow however
+/// many real records happen to follow it. `0xffff` falls in the
+/// reserved/broadcast range, which servers are never assigned an address
+/// from, so it is safe to use as a two-byte marker.
 #[derive(Debug, BinRead, PartialEq)]
 #[br(big)]
-pub(crate) struct RawServerAddress {
-    ip: [u8; 4],
-    port: u16,
+pub(crate) enum RawServerAddress {
+    #[br(magic = 0xffffu16)]
+    V6([u8; 16], u16),
+
+    V4([u8; 4], u16),
 }
 
 impl Display for RawServerAddress {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let ip_str = self.ip.map(|b| b.to_string()).join(".");
-        write!(f, "{}:{}", ip_str, self.port)
+        match self {
+            RawServerAddress::V4(ip, port) => {
+                let ip_str = ip.map(|b| b.to_string()).join(".");
+                write!(f, "{}:{}", ip_str, port)
+            }
+            RawServerAddress::V6(ip, port) => {
+                write!(f, "[{}]:{}", Ipv6Addr::from(*ip), port)
+            }
+        }
+    }
+}
+
+/// A single server address returned by a master server.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ServerAddress {
+    pub ip: String,
+    pub port: u16,
+}
+
+impl From<RawServerAddress> for ServerAddress {
+    fn from(raw: RawServerAddress) -> Self {
+        match raw {
+            RawServerAddress::V4(ip, port) => ServerAddress {
+                ip: ip.map(|b| b.to_string()).join("."),
+                port,
+            },
+            RawServerAddress::V6(ip, port) => ServerAddress {
+                ip: Ipv6Addr::from(ip).to_string(),
+                port,
+            },
+        }
+    }
+}
+
+impl Display for ServerAddress {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.ip.contains(':') {
+            write!(f, "[{}]:{}", self.ip, self.port)
+        } else {
+            write!(f, "{}:{}", self.ip, self.port)
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::server_address::RawServerAddress;
+    use crate::server_address::{RawServerAddress, ServerAddress};
     use binrw::BinRead;
     use pretty_assertions::assert_eq;
     use std::io::Cursor;
 
     #[test]
-    fn test_read() {
+    fn test_read_v4() {
         assert_eq!(
             RawServerAddress::read(&mut Cursor::new(&[192, 168, 1, 1, 117, 48])).unwrap(),
-            RawServerAddress {
-                ip: [192, 168, 1, 1],
+            RawServerAddress::V4([192, 168, 1, 1], 30000)
+        );
+    }
+
+    #[test]
+    fn test_read_v6() {
+        #[rustfmt::skip]
+        let bytes = [
+            0xff, 0xff,
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1,
+            117, 48,
+        ];
+        assert_eq!(
+            RawServerAddress::read(&mut Cursor::new(&bytes)).unwrap(),
+            RawServerAddress::V6([0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1], 30000)
+        );
+    }
+
+    #[test]
+    fn test_read_v4_with_leading_octet_matching_former_marker_byte() {
+        // Regression test: a V4 address starting with octet 6 must not be
+        // misread as the start of an (unrelated) V6 marker sequence.
+        assert_eq!(
+            RawServerAddress::read(&mut Cursor::new(&[6, 1, 2, 3, 117, 48])).unwrap(),
+            RawServerAddress::V4([6, 1, 2, 3], 30000)
+        );
+    }
+
+    #[test]
+    fn test_display_v4() {
+        let address = RawServerAddress::V4([192, 168, 1, 1], 30000);
+        assert_eq!(address.to_string(), "192.168.1.1:30000".to_string());
+    }
+
+    #[test]
+    fn test_display_v6() {
+        let address =
+            RawServerAddress::V6([0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1], 30000);
+        assert_eq!(address.to_string(), "[::1]:30000".to_string());
+    }
+
+    #[test]
+    fn test_server_address_from_raw_v4() {
+        let raw = RawServerAddress::V4([192, 168, 1, 1], 30000);
+        assert_eq!(
+            ServerAddress::from(raw),
+            ServerAddress {
+                ip: "192.168.1.1".to_string(),
+                port: 30000
+            }
+        );
+    }
+
+    #[test]
+    fn test_server_address_from_raw_v6() {
+        let raw = RawServerAddress::V6([0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1], 30000);
+        assert_eq!(
+            ServerAddress::from(raw),
+            ServerAddress {
+                ip: "::1".to_string(),
                 port: 30000
             }
         );
     }
 
     #[test]
-    fn test_display() {
-        let address = RawServerAddress {
-            ip: [192, 168, 1, 1],
+    fn test_server_address_display() {
+        let address = ServerAddress {
+            ip: "192.168.1.1".to_string(),
             port: 30000,
         };
         assert_eq!(address.to_string(), "192.168.1.1:30000".to_string());
     }
+
+    #[test]
+    fn test_server_address_display_v6() {
+        let address = ServerAddress {
+            ip: "::1".to_string(),
+            port: 30000,
+        };
+        assert_eq!(address.to_string(), "[::1]:30000".to_string());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_server_address_serializes_as_json() {
+        let address = ServerAddress {
+            ip: "192.168.1.1".to_string(),
+            port: 30000,
+        };
+        assert_eq!(
+            serde_json::to_value(&address).unwrap(),
+            serde_json::json!({"ip": "192.168.1.1", "port": 30000})
+        );
+    }
 }
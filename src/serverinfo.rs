@@ -0,0 +1,243 @@
+//! Per-server status querying: round-trip ping timing and parsed `ServerInfo`.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use tokio::sync::Semaphore;
+
+use crate::server_address::ServerAddress;
+use crate::tinyudp::{self, Transport, UdpTransport};
+
+const STATUS_MSG: &[u8] = b"\xff\xff\xff\xffstatus\n";
+const RESPONSE_HEADER: &[u8] = b"\xff\xff\xff\xffn\\";
+
+/// Info string and player count parsed from a QuakeWorld `status` reply.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ServerInfo {
+    /// Key/value pairs from the backslash-delimited info string.
+    pub info: BTreeMap<String, String>,
+
+    /// Number of connected players, counted from the player lines.
+    pub player_count: usize,
+}
+
+/// The outcome of querying a single server.
+#[derive(Debug)]
+pub struct ServerResult {
+    pub address: ServerAddress,
+    pub kind: ServerResultKind,
+}
+
+/// Per-server query outcome, mirroring the xash3d query tool's `ServerResultKind`.
+#[derive(Debug)]
+pub enum ServerResultKind {
+    Ok { ping_ms: f32, info: ServerInfo },
+    Timeout,
+    Invalid,
+}
+
+/// Query the `status` of many servers concurrently.
+///
+/// `concurrency` bounds how many queries are in flight at once, so that
+/// querying thousands of servers doesn't exhaust local sockets.
+///
+/// # Example
+///
+/// ```
+/// use std::time::Duration;
+/// use masterstat::ServerAddress;
+///
+/// async fn test() {
+///     let servers = vec![ServerAddress { ip: "127.0.0.1".to_string(), port: 27500 }];
+///     let timeout = Duration::from_secs(2);
+///     let results = masterstat::query_servers(&servers, timeout, 100).await;
+///     println!("queried {} servers", results.len());
+/// }
+/// ```
+pub async fn query_servers(
+    addresses: &[ServerAddress],
+    timeout: Duration,
+    concurrency: usize,
+) -> Vec<ServerResult> {
+    query_servers_with(&UdpTransport, addresses, timeout, concurrency).await
+}
+
+/// Same as [`query_servers`], but over a caller-supplied [`Transport`]
+/// instead of real UDP sockets — useful for testing without network access.
+pub async fn query_servers_with(
+    transport: &(impl Transport + Sync),
+    addresses: &[ServerAddress],
+    timeout: Duration,
+    concurrency: usize,
+) -> Vec<ServerResult> {
+    let semaphore = Arc::new(Semaphore::new(concurrency.max(1)));
+
+    let tasks = addresses.iter().cloned().map(|address| {
+        let semaphore = semaphore.clone();
+        async move {
+            let _permit = semaphore
+                .acquire_owned()
+                .await
+                .expect("semaphore is never closed");
+            let kind = query_one(transport, &address, timeout).await;
+            ServerResult { address, kind }
+        }
+    });
+
+    futures::future::join_all(tasks).await
+}
+
+async fn query_one(
+    transport: &impl Transport,
+    address: &ServerAddress,
+    timeout: Duration,
+) -> ServerResultKind {
+    let started = Instant::now();
+    let response = transport
+        .request(
+            &address.to_string(),
+            STATUS_MSG,
+            tinyudp::Options {
+                timeout,
+                buffer_size: 32 * 1024, // 32 kb
+                retries: 0,
+                retry_delay: Duration::ZERO,
+            },
+        )
+        .await;
+
+    match response {
+        Ok(body) => match parse_status_response(&body) {
+            Some(info) => ServerResultKind::Ok {
+                ping_ms: started.elapsed().as_secs_f32() * 1000.0,
+                info,
+            },
+            None => ServerResultKind::Invalid,
+        },
+        Err(error) => match error.downcast_ref::<tinyudp::TinyudpError>() {
+            Some(tinyudp::TinyudpError::TimeoutReached { .. }) => ServerResultKind::Timeout,
+            _ => ServerResultKind::Invalid,
+        },
+    }
+}
+
+fn parse_status_response(response: &[u8]) -> Option<ServerInfo> {
+    if !response.starts_with(RESPONSE_HEADER) {
+        return None;
+    }
+
+    let body = String::from_utf8_lossy(&response[RESPONSE_HEADER.len()..]);
+    let mut lines = body.split('\n');
+    let info_line = lines.next()?;
+
+    // The header already consumes the backslash that opens the info string;
+    // a second leading backslash, if present, is skipped as well.
+    let info_line = info_line.strip_prefix('\\').unwrap_or(info_line);
+    let mut fields = info_line.split('\\');
+    let mut info = BTreeMap::new();
+    while let (Some(key), Some(value)) = (fields.next(), fields.next()) {
+        info.insert(key.to_string(), value.to_string());
+    }
+
+    let player_count = lines.filter(|line| !line.is_empty()).count();
+
+    Some(ServerInfo { info, player_count })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_parse_status_response() {
+        // invalid response header
+        {
+            let response = b"\xff\xff\xff\xffnope";
+            assert_eq!(parse_status_response(response), None);
+        }
+
+        // valid response with two players
+        {
+            let mut response = RESPONSE_HEADER.to_vec();
+            response.extend_from_slice(b"\\hostname\\quake.se KTX\\maxclients\\8\n");
+            response.extend_from_slice(b"0 0 0 0 \"player1\" \"\"\n");
+            response.extend_from_slice(b"1 0 0 0 \"player2\" \"\"\n");
+
+            let info = parse_status_response(&response).unwrap();
+            assert_eq!(info.info.get("hostname").unwrap(), "quake.se KTX");
+            assert_eq!(info.info.get("maxclients").unwrap(), "8");
+            assert_eq!(info.player_count, 2);
+        }
+    }
+
+    struct ScriptedTransport;
+
+    impl Transport for ScriptedTransport {
+        async fn request(
+            &self,
+            target: &str,
+            _msg: &[u8],
+            _opts: tinyudp::Options,
+        ) -> anyhow::Result<Vec<u8>> {
+            match target {
+                "timeout:0" => Err(tinyudp::TinyudpError::TimeoutReached { attempts: 1 }.into()),
+                "invalid:0" => Ok(b"not a status response".to_vec()),
+                _ => {
+                    let mut response = RESPONSE_HEADER.to_vec();
+                    response.extend_from_slice(b"\\hostname\\quake.se KTX\\maxclients\\8\n");
+                    response.extend_from_slice(b"0 0 0 0 \"player1\" \"\"\n");
+                    Ok(response)
+                }
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_query_servers_with_mock_transport() {
+        let addresses = vec![
+            ServerAddress {
+                ip: "ok".to_string(),
+                port: 0,
+            },
+            ServerAddress {
+                ip: "timeout".to_string(),
+                port: 0,
+            },
+            ServerAddress {
+                ip: "invalid".to_string(),
+                port: 0,
+            },
+        ];
+
+        let results =
+            query_servers_with(&ScriptedTransport, &addresses, Duration::from_secs(1), 10).await;
+
+        assert_eq!(results.len(), 3);
+        match &results[0].kind {
+            ServerResultKind::Ok { info, .. } => assert_eq!(info.player_count, 1),
+            other => panic!("expected Ok, got {:?}", other),
+        }
+        assert!(matches!(results[1].kind, ServerResultKind::Timeout));
+        assert!(matches!(results[2].kind, ServerResultKind::Invalid));
+    }
+
+    #[tokio::test]
+    #[cfg_attr(feature = "ci", ignore)]
+    async fn test_query_servers() {
+        let servers = vec![ServerAddress {
+            ip: "quake.se".to_string(),
+            port: 28501,
+        }];
+        let results = query_servers(&servers, Duration::from_secs(2), 10).await;
+
+        assert_eq!(results.len(), 1);
+        match &results[0].kind {
+            ServerResultKind::Ok { info, .. } => {
+                assert!(info.info.get("hostname").unwrap().contains("QUAKE.SE"));
+            }
+            other => panic!("expected Ok, got {:?}", other),
+        }
+    }
+}
@@ -15,29 +15,40 @@ pub enum TinyudpError {
     #[error("failed to receive message: {0}")]
     ReceiveFailed(#[source] std::io::Error),
 
-    #[error("timeout reached while waiting for response")]
-    TimeoutReached,
+    #[error("timeout reached while waiting for response after {attempts} attempt(s)")]
+    TimeoutReached { attempts: u8 },
 }
 
 pub async fn send_and_receive(
-    target: impl ToSocketAddrs,
+    target: impl ToSocketAddrs + Clone,
     message: &[u8],
     options: Options,
 ) -> Result<Vec<u8>, TinyudpError> {
-    let socket = bind().await?;
-    socket
-        .send_to(message, target)
-        .await
-        .map_err(TinyudpError::SendFailed)?;
+    let mut attempts = 0u8;
+
+    loop {
+        attempts += 1;
 
-    let mut buffer = vec![0; options.buffer_size];
-    let (bytes_read, _) = tokio::select! {
-        _ = tokio::time::sleep(options.timeout) => Err(TinyudpError::TimeoutReached),
-        res = socket.recv_from(&mut buffer) => res.map_err(TinyudpError::ReceiveFailed),
-    }?;
+        // A fresh socket is bound on ephemeral port 0 for every attempt, so
+        // retries naturally go out from a new source port.
+        let socket = bind().await?;
+        socket
+            .send_to(message, target.clone())
+            .await
+            .map_err(TinyudpError::SendFailed)?;
 
-    let response = buffer[..bytes_read].to_vec();
-    Ok(response)
+        let mut buffer = vec![0; options.buffer_size];
+        let received = tokio::select! {
+            _ = tokio::time::sleep(options.timeout) => None,
+            res = socket.recv_from(&mut buffer) => Some(res.map_err(TinyudpError::ReceiveFailed)?),
+        };
+
+        match received {
+            Some((bytes_read, _)) => return Ok(buffer[..bytes_read].to_vec()),
+            None if attempts <= options.retries => tokio::time::sleep(options.retry_delay).await,
+            None => return Err(TinyudpError::TimeoutReached { attempts }),
+        }
+    }
 }
 
 async fn bind() -> Result<UdpSocket, TinyudpError> {
@@ -51,6 +62,58 @@ async fn bind() -> Result<UdpSocket, TinyudpError> {
 pub struct Options {
     pub timeout: Duration,
     pub buffer_size: usize,
+
+    /// Additional attempts to make, each from a fresh socket/source port,
+    /// after the initial send times out.
+    pub retries: u8,
+
+    /// Delay before each retry.
+    pub retry_delay: Duration,
+}
+
+/// A request/response transport, abstracting over how a message actually
+/// reaches a server. Queries are generic over this trait so tests can inject
+/// a canned-response transport instead of hitting real servers.
+///
+/// The returned future is required to be `Send` so that callers (such as
+/// [`crate::command::server_addresses_from_many_with`]) can await it from
+/// inside a spawned task.
+pub trait Transport {
+    fn request(
+        &self,
+        target: &str,
+        msg: &[u8],
+        opts: Options,
+    ) -> impl std::future::Future<Output = Result<Vec<u8>>> + Send;
+}
+
+/// The default [`Transport`], backed by a real UDP socket.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct UdpTransport;
+
+impl Transport for UdpTransport {
+    async fn request(&self, target: &str, msg: &[u8], opts: Options) -> Result<Vec<u8>> {
+        Ok(send_and_receive(target, msg, opts).await?)
+    }
+}
+
+/// Test-only [`Transport`] mocks shared across modules that exercise `_with`
+/// entry points without hitting the network.
+#[cfg(test)]
+pub(crate) mod test_support {
+    use super::{Options, Transport};
+    use anyhow::Result;
+
+    /// A [`Transport`] that ignores the target and always returns the same
+    /// canned response.
+    #[derive(Clone)]
+    pub(crate) struct FixedResponseTransport(pub(crate) Vec<u8>);
+
+    impl Transport for FixedResponseTransport {
+        async fn request(&self, _target: &str, _msg: &[u8], _opts: Options) -> Result<Vec<u8>> {
+            Ok(self.0.clone())
+        }
+    }
 }
 
 #[cfg(test)]
@@ -66,10 +129,32 @@ mod tests {
             Options {
                 timeout: Duration::from_secs_f32(0.2),
                 buffer_size: 32 * 1024,
+                retries: 0,
+                retry_delay: Duration::ZERO,
             },
         )
         .await?;
         assert!(String::from_utf8_lossy(&response).contains("QUAKE.SE KTX"));
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_send_and_receive_retries_on_timeout() {
+        let result = send_and_receive(
+            "127.0.0.1:1",
+            b"\xff\xff\xff\xffstatus",
+            Options {
+                timeout: Duration::from_millis(50),
+                buffer_size: 1024,
+                retries: 2,
+                retry_delay: Duration::from_millis(10),
+            },
+        )
+        .await;
+
+        assert!(matches!(
+            result,
+            Err(TinyudpError::TimeoutReached { attempts: 3 })
+        ));
+    }
 }